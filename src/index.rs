@@ -0,0 +1,122 @@
+use crate::ffi;
+
+/// Generates a shadow index buffer that can be used for more efficient rendering when only a subset of the vertex
+/// attributes is necessary.
+///
+/// All vertices that are binary equivalent over the `vertex_size` bytes (typically just position) starting at
+/// `vertex_stride` spacing map to the same vertex, so the resulting index buffer has the same length as `indices`
+/// but refers to fewer unique vertices. This is important since vertex cache effectiveness depends on unique vertex
+/// count in the resulting index buffer, not the original one; a smaller shadow index buffer means a depth pre-pass
+/// or shadow map pass can run faster even though the vertex buffer itself isn't modified.
+///
+/// The resulting index buffer can be used together with the original vertex buffer, as positions of the attribute
+/// subset are guaranteed to not be reordered; pass the same vertex buffer used by the main pass.
+pub fn generate_shadow_index_buffer<T>(
+    indices: &[u32],
+    vertices: &[T],
+    vertex_count: usize,
+    vertex_size: usize,
+    vertex_stride: usize,
+) -> Vec<u32> {
+    let mut result: Vec<u32> = vec![0; indices.len()];
+    unsafe {
+        ffi::meshopt_generateShadowIndexBuffer(
+            result.as_mut_ptr(),
+            indices.as_ptr(),
+            indices.len(),
+            vertices.as_ptr().cast(),
+            vertex_count,
+            vertex_size,
+            vertex_stride,
+        );
+    }
+    result
+}
+
+/// Generates a shadow index buffer from multiple vertex streams, analogous to `generate_shadow_index_buffer` but
+/// allowing the attribute subset used for deduplication to be assembled from several separately-strided streams
+/// (mirroring `generate_vertex_remap_multi`).
+pub fn generate_shadow_index_buffer_multi(
+    indices: &[u32],
+    vertex_count: usize,
+    streams: &[crate::VertexStream<'_>],
+) -> Vec<u32> {
+    let streams: Vec<ffi::meshopt_Stream> = streams
+        .iter()
+        .map(|stream| ffi::meshopt_Stream {
+            data: stream.data.cast(),
+            size: stream.size,
+            stride: stream.stride,
+        })
+        .collect();
+    let mut result: Vec<u32> = vec![0; indices.len()];
+    unsafe {
+        ffi::meshopt_generateShadowIndexBufferMulti(
+            result.as_mut_ptr(),
+            indices.as_ptr(),
+            indices.len(),
+            vertex_count,
+            streams.as_ptr(),
+            streams.len(),
+        );
+    }
+    result
+}
+
+/// Generates an index buffer that can be used for GPU-side geometry shader triangle adjacency.
+///
+/// The index buffer is `indices.len() * 2` elements long and follows the `GL_TRIANGLES_ADJACENCY` layout: for each
+/// input triangle, positions 0/2/4 of the corresponding output hextuple are the triangle's own three corners, while
+/// positions 1/3/5 are the vertex of the neighboring triangle across the opposite edge (or the triangle's own
+/// vertex if the edge is a boundary with no neighbor). Coincident but unwelded vertices are treated as one via an
+/// internal position-only vertex remap, so this works on vertex buffers that were never deduplicated.
+///
+/// This is used to generate input for geometry shaders that need to know adjacent vertices for each triangle, for
+/// example to implement silhouette detection or crack-free displacement mapping.
+pub fn generate_adjacency_index_buffer(
+    indices: &[u32],
+    vertex_positions: &[f32],
+    vertex_count: usize,
+    position_stride: usize,
+) -> Vec<u32> {
+    let mut result: Vec<u32> = vec![0; indices.len() * 2];
+    unsafe {
+        ffi::meshopt_generateAdjacencyIndexBuffer(
+            result.as_mut_ptr(),
+            indices.as_ptr(),
+            indices.len(),
+            vertex_positions.as_ptr(),
+            vertex_count,
+            position_stride,
+        );
+    }
+    result
+}
+
+/// Generates an index buffer that can be used for PN-AEN tessellation with crack-free displacement.
+///
+/// The index buffer is `indices.len() * 4` elements long; each source triangle expands to twelve indices laid out
+/// per McDonald & Kilgard's adjacent-edge-normals scheme: indices 0-2 are the triangle's own three corners, indices
+/// 3-8 are its three edges as endpoint pairs, and indices 9-11 are the "dominant" vertex per corner used to resolve
+/// normal/UV seams consistently across patches. Both the edge pairs and the dominant vertices are derived from a
+/// position-only vertex remap, so that two triangles sharing an edge agree on its ordering and dominant vertex
+/// regardless of which vertex copies they originally referenced - this is what keeps adjacent patches crack-free.
+pub fn generate_tessellation_index_buffer(
+    indices: &[u32],
+    vertex_positions: &[f32],
+    vertex_count: usize,
+    position_stride: usize,
+) -> Vec<u32> {
+    let mut result: Vec<u32> = vec![0; indices.len() * 4];
+    unsafe {
+        ffi::meshopt_generateTessellationIndexBuffer(
+            result.as_mut_ptr(),
+            indices.as_ptr(),
+            indices.len(),
+            vertex_positions.as_ptr(),
+            vertex_count,
+            position_stride,
+        );
+    }
+    result
+}
@@ -1,6 +1,9 @@
 use crate::{ffi, VertexStream};
 use std::mem;
 
+#[cfg(feature = "rust-backend")]
+use crate::rust_backend;
+
 /// Generates a vertex remap table from the vertex buffer and an optional index buffer and returns number of unique vertices.
 ///
 /// As a result, all vertices that are binary equivalent map to the same (new) location, with no gaps in the resulting sequence.
@@ -17,28 +20,40 @@ pub fn generate_vertex_sized_remap<T>(
     indices: Option<&[u32]>,
 ) -> (usize, Vec<u32>) {
     let vertex_count = vertices.len() / (vertex_size / mem::size_of::<T>());
-    let mut remap: Vec<u32> = vec![0; vertex_count];
-    let vertex_count = unsafe {
-        match indices {
-            Some(indices) => ffi::meshopt_generateVertexRemap(
-                remap.as_mut_ptr().cast(),
-                indices.as_ptr().cast(),
-                indices.len(),
-                vertices.as_ptr().cast(),
-                vertex_count,
-                vertex_size,
-            ),
-            None => ffi::meshopt_generateVertexRemap(
-                remap.as_mut_ptr(),
-                std::ptr::null(),
-                vertex_count,
-                vertices.as_ptr().cast(),
-                vertex_count,
-                vertex_size,
-            ),
-        }
-    };
-    (vertex_count, remap)
+
+    #[cfg(feature = "rust-backend")]
+    {
+        let bytes = unsafe {
+            std::slice::from_raw_parts(vertices.as_ptr().cast::<u8>(), vertex_count * vertex_size)
+        };
+        rust_backend::generate_vertex_remap(bytes, vertex_count, vertex_size, indices)
+    }
+
+    #[cfg(not(feature = "rust-backend"))]
+    {
+        let mut remap: Vec<u32> = vec![0; vertex_count];
+        let vertex_count = unsafe {
+            match indices {
+                Some(indices) => ffi::meshopt_generateVertexRemap(
+                    remap.as_mut_ptr().cast(),
+                    indices.as_ptr().cast(),
+                    indices.len(),
+                    vertices.as_ptr().cast(),
+                    vertex_count,
+                    vertex_size,
+                ),
+                None => ffi::meshopt_generateVertexRemap(
+                    remap.as_mut_ptr(),
+                    std::ptr::null(),
+                    vertex_count,
+                    vertices.as_ptr().cast(),
+                    vertex_count,
+                    vertex_size,
+                ),
+            }
+        };
+        (vertex_count, remap)
+    }
 }
 
 /// Generates a vertex remap table from multiple vertex streams and an optional index buffer and returns number of unique vertices.
@@ -54,36 +69,59 @@ pub fn generate_vertex_remap_multi(
     streams: &[VertexStream<'_>],
     indices: Option<&[u32]>,
 ) -> (usize, Vec<u32>) {
-    let streams: Vec<ffi::meshopt_Stream> = streams
-        .iter()
-        .map(|stream| ffi::meshopt_Stream {
-            data: stream.data.cast(),
-            size: stream.size,
-            stride: stream.stride,
-        })
-        .collect();
-    let mut remap: Vec<u32> = vec![0; vertex_count];
-    let vertex_count = unsafe {
-        match indices {
-            Some(indices) => ffi::meshopt_generateVertexRemapMulti(
-                remap.as_mut_ptr(),
-                indices.as_ptr(),
-                indices.len(),
-                vertex_count,
-                streams.as_ptr(),
-                streams.len(),
-            ),
-            None => ffi::meshopt_generateVertexRemapMulti(
-                remap.as_mut_ptr(),
-                std::ptr::null(),
-                vertex_count,
-                vertex_count,
-                streams.as_ptr(),
-                streams.len(),
-            ),
-        }
-    };
-    (vertex_count, remap)
+    #[cfg(feature = "rust-backend")]
+    {
+        let streams: Vec<rust_backend::RustStream<'_>> = streams
+            .iter()
+            .map(|stream| {
+                let len = if vertex_count == 0 {
+                    0
+                } else {
+                    (vertex_count - 1) * stream.stride + stream.size
+                };
+                rust_backend::RustStream {
+                    data: unsafe { std::slice::from_raw_parts(stream.data.cast::<u8>(), len) },
+                    size: stream.size,
+                    stride: stream.stride,
+                }
+            })
+            .collect();
+        rust_backend::generate_vertex_remap_multi(vertex_count, &streams, indices)
+    }
+
+    #[cfg(not(feature = "rust-backend"))]
+    {
+        let streams: Vec<ffi::meshopt_Stream> = streams
+            .iter()
+            .map(|stream| ffi::meshopt_Stream {
+                data: stream.data.cast(),
+                size: stream.size,
+                stride: stream.stride,
+            })
+            .collect();
+        let mut remap: Vec<u32> = vec![0; vertex_count];
+        let vertex_count = unsafe {
+            match indices {
+                Some(indices) => ffi::meshopt_generateVertexRemapMulti(
+                    remap.as_mut_ptr(),
+                    indices.as_ptr(),
+                    indices.len(),
+                    vertex_count,
+                    streams.as_ptr(),
+                    streams.len(),
+                ),
+                None => ffi::meshopt_generateVertexRemapMulti(
+                    remap.as_mut_ptr(),
+                    std::ptr::null(),
+                    vertex_count,
+                    vertex_count,
+                    streams.as_ptr(),
+                    streams.len(),
+                ),
+            }
+        };
+        (vertex_count, remap)
+    }
 }
 
 /// Generate index buffer from the source index buffer and remap table generated by `generate_vertex_remap`.
@@ -183,3 +221,29 @@ pub fn remap_vertex_buffer_sized_in_place<T: Clone + Default>(
         );
     }
 }
+
+/// Generates a vertex remap table by deduplicating vertices on a user-supplied key instead of binary equality,
+/// returning the number of unique vertices in the same `(usize, Vec<u32>)` shape as `generate_vertex_remap` so it
+/// composes with the existing `remap_vertex_buffer`/`remap_index_buffer` functions.
+///
+/// This is useful for welding vertices that are only "the same" up to some tolerance - e.g. positions quantized to
+/// a grid, or normals within an angular threshold - which binary-equality dedup in `generate_vertex_remap` misses.
+/// `key` should map each vertex to a hashable, equality-comparable value (such as a quantized `[i32; 3]`); vertices
+/// are visited in order and the first vertex to produce a given key claims the next new id, so the result is
+/// deterministic and stable across runs.
+pub fn weld_vertices<T, K, F>(vertices: &[T], mut key: F) -> (usize, Vec<u32>)
+where
+    K: Eq + std::hash::Hash,
+    F: FnMut(&T) -> K,
+{
+    let mut ids: std::collections::HashMap<K, u32> = std::collections::HashMap::with_capacity(vertices.len());
+    let mut remap: Vec<u32> = Vec::with_capacity(vertices.len());
+
+    for vertex in vertices {
+        let next_id = ids.len() as u32;
+        let id = *ids.entry(key(vertex)).or_insert(next_id);
+        remap.push(id);
+    }
+
+    (ids.len(), remap)
+}
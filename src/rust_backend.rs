@@ -0,0 +1,260 @@
+//! Pure-Rust reimplementation of the binary-equality vertex remap, used in place of `ffi::meshopt_generateVertexRemap`
+//! and `ffi::meshopt_generateVertexRemapMulti` when the `rust-backend` feature is enabled. Only these two remap
+//! generators are replaced: `remap_index_buffer`, `remap_vertex_buffer` and their variants, as well as the index
+//! buffer generators, still call into the bundled C library, so it is still linked with this feature enabled.
+//!
+//! The table is open-addressed with linear probing, sized to the next power of two at or above `1.25 * vertex_count`
+//! so the load factor stays low, and uses a MurmurHash2-style mix to hash each vertex's raw bytes. Collisions are
+//! resolved with a byte-for-byte compare, so the results are equivalent to the binary-equality semantics of
+//! `generate_vertex_remap`.
+
+const MURMUR2_M: u32 = 0x5bd1e995;
+const MURMUR2_R: u32 = 24;
+
+fn hash_bytes(data: &[u8]) -> u32 {
+    let mut h: u32 = data.len() as u32;
+
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(MURMUR2_M);
+        k ^= k >> MURMUR2_R;
+        k = k.wrapping_mul(MURMUR2_M);
+        h = h.wrapping_mul(MURMUR2_M);
+        h ^= k;
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let mut k: u32 = 0;
+        for (i, &byte) in tail.iter().enumerate() {
+            k |= (byte as u32) << (8 * i);
+        }
+        h ^= k;
+        h = h.wrapping_mul(MURMUR2_M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(MURMUR2_M);
+    h ^= h >> 15;
+    h
+}
+
+fn table_size_for(vertex_count: usize) -> usize {
+    ((vertex_count * 5 + 3) / 4).max(1).next_power_of_two()
+}
+
+/// Pure-Rust equivalent of `ffi::meshopt_generateVertexRemap`.
+pub fn generate_vertex_remap(
+    vertices: &[u8],
+    vertex_count: usize,
+    vertex_size: usize,
+    indices: Option<&[u32]>,
+) -> (usize, Vec<u32>) {
+    let vertex_of = |i: usize| &vertices[i * vertex_size..i * vertex_size + vertex_size];
+
+    let mask = table_size_for(vertex_count) - 1;
+    let mut table: Vec<i64> = vec![-1; mask + 1];
+    let mut remap: Vec<u32> = vec![u32::MAX; vertex_count];
+    let mut next_id: u32 = 0;
+
+    let mut visit = |vertex_index: usize| {
+        if remap[vertex_index] != u32::MAX {
+            return;
+        }
+        let bytes = vertex_of(vertex_index);
+        let mut slot = hash_bytes(bytes) as usize & mask;
+        loop {
+            match table[slot] {
+                -1 => {
+                    table[slot] = vertex_index as i64;
+                    remap[vertex_index] = next_id;
+                    next_id += 1;
+                    break;
+                }
+                existing if vertex_of(existing as usize) == bytes => {
+                    remap[vertex_index] = remap[existing as usize];
+                    break;
+                }
+                _ => slot = (slot + 1) & mask,
+            }
+        }
+    };
+
+    match indices {
+        Some(indices) => {
+            for &index in indices {
+                visit(index as usize);
+            }
+        }
+        None => {
+            for vertex_index in 0..vertex_count {
+                visit(vertex_index);
+            }
+        }
+    }
+
+    (next_id as usize, remap)
+}
+
+pub struct RustStream<'a> {
+    pub data: &'a [u8],
+    pub size: usize,
+    pub stride: usize,
+}
+
+/// Pure-Rust equivalent of `ffi::meshopt_generateVertexRemapMulti`.
+pub fn generate_vertex_remap_multi(
+    vertex_count: usize,
+    streams: &[RustStream<'_>],
+    indices: Option<&[u32]>,
+) -> (usize, Vec<u32>) {
+    fn stream_bytes<'a>(stream: &RustStream<'a>, i: usize) -> &'a [u8] {
+        &stream.data[i * stream.stride..i * stream.stride + stream.size]
+    }
+
+    let vertices_equal = |a: usize, b: usize| streams.iter().all(|stream| stream_bytes(stream, a) == stream_bytes(stream, b));
+
+    let hash_vertex = |i: usize| {
+        streams
+            .iter()
+            .fold(0u32, |h, stream| h.rotate_left(7) ^ hash_bytes(stream_bytes(stream, i)))
+    };
+
+    let mask = table_size_for(vertex_count) - 1;
+    let mut table: Vec<i64> = vec![-1; mask + 1];
+    let mut remap: Vec<u32> = vec![u32::MAX; vertex_count];
+    let mut next_id: u32 = 0;
+
+    let mut visit = |vertex_index: usize| {
+        if remap[vertex_index] != u32::MAX {
+            return;
+        }
+        let mut slot = hash_vertex(vertex_index) as usize & mask;
+        loop {
+            match table[slot] {
+                -1 => {
+                    table[slot] = vertex_index as i64;
+                    remap[vertex_index] = next_id;
+                    next_id += 1;
+                    break;
+                }
+                existing if vertices_equal(existing as usize, vertex_index) => {
+                    remap[vertex_index] = remap[existing as usize];
+                    break;
+                }
+                _ => slot = (slot + 1) & mask,
+            }
+        }
+    };
+
+    match indices {
+        Some(indices) => {
+            for &index in indices {
+                visit(index as usize);
+            }
+        }
+        None => {
+            for vertex_index in 0..vertex_count {
+                visit(vertex_index);
+            }
+        }
+    }
+
+    (next_id as usize, remap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// O(n^2) byte-comparison dedup with the same visitation order as `generate_vertex_remap`, used as a
+    /// ground truth for the binary-equality semantics the hash table above is expected to reproduce exactly.
+    fn naive_remap(
+        vertices: &[u8],
+        vertex_count: usize,
+        vertex_size: usize,
+        indices: Option<&[u32]>,
+    ) -> (usize, Vec<u32>) {
+        let vertex_of = |i: usize| &vertices[i * vertex_size..i * vertex_size + vertex_size];
+        let mut remap = vec![u32::MAX; vertex_count];
+        let mut unique: Vec<usize> = Vec::new();
+
+        let mut visit = |i: usize| {
+            if remap[i] != u32::MAX {
+                return;
+            }
+            match unique.iter().position(|&u| vertex_of(u) == vertex_of(i)) {
+                Some(pos) => remap[i] = pos as u32,
+                None => {
+                    remap[i] = unique.len() as u32;
+                    unique.push(i);
+                }
+            }
+        };
+
+        match indices {
+            Some(indices) => {
+                for &index in indices {
+                    visit(index as usize);
+                }
+            }
+            None => {
+                for i in 0..vertex_count {
+                    visit(i);
+                }
+            }
+        }
+
+        (unique.len(), remap)
+    }
+
+    #[test]
+    fn matches_naive_dedup_unindexed() {
+        let vertices: [[u8; 4]; 5] = [[1, 0, 0, 0], [2, 0, 0, 0], [1, 0, 0, 0], [3, 0, 0, 0], [2, 0, 0, 0]];
+        let bytes: Vec<u8> = vertices.iter().flatten().copied().collect();
+
+        let (count, remap) = generate_vertex_remap(&bytes, 5, 4, None);
+        let (naive_count, naive_remap) = naive_remap(&bytes, 5, 4, None);
+
+        assert_eq!(count, naive_count);
+        assert_eq!(remap, naive_remap);
+    }
+
+    #[test]
+    fn matches_naive_dedup_indexed_with_gaps() {
+        let vertices: [[u8; 4]; 5] = [[1, 0, 0, 0], [2, 0, 0, 0], [1, 0, 0, 0], [3, 0, 0, 0], [2, 0, 0, 0]];
+        let bytes: Vec<u8> = vertices.iter().flatten().copied().collect();
+        // Vertices 3 and 4 are never referenced by an index.
+        let indices = [0u32, 1, 2, 1];
+
+        let (count, remap) = generate_vertex_remap(&bytes, 5, 4, Some(&indices));
+        let (naive_count, naive_remap) = naive_remap(&bytes, 5, 4, Some(&indices));
+
+        assert_eq!(count, naive_count);
+        assert_eq!(remap, naive_remap);
+        assert_eq!(remap[3], u32::MAX);
+        assert_eq!(remap[4], u32::MAX);
+    }
+
+    #[test]
+    fn matches_naive_dedup_multi_stream() {
+        let positions: [[u8; 4]; 4] = [[0, 0, 0, 0], [1, 0, 0, 0], [0, 0, 0, 0], [2, 0, 0, 0]];
+        let normals: [[u8; 4]; 4] = [[9, 0, 0, 0], [9, 0, 0, 0], [9, 0, 0, 0], [9, 0, 0, 0]];
+        let position_bytes: Vec<u8> = positions.iter().flatten().copied().collect();
+        let normal_bytes: Vec<u8> = normals.iter().flatten().copied().collect();
+
+        let streams = [
+            RustStream { data: &position_bytes, size: 4, stride: 4 },
+            RustStream { data: &normal_bytes, size: 4, stride: 4 },
+        ];
+
+        let (count, remap) = generate_vertex_remap_multi(4, &streams, None);
+
+        // Vertices 0 and 2 share both position and normal, so they collapse to the same id.
+        assert_eq!(count, 3);
+        assert_eq!(remap[0], remap[2]);
+        assert_ne!(remap[0], remap[1]);
+        assert_ne!(remap[0], remap[3]);
+    }
+}